@@ -0,0 +1,102 @@
+use pyo3::prelude::*;
+
+/// known mappings from Petra's `projection` strings to EPSG codes
+///
+/// these are the state-plane zones we've actually seen in the wild;
+/// anything not listed here falls back to `proj4_from_parameters`, which
+/// builds a projection definition directly from `cm`/`rlat`/`datum`
+///
+/// the NAD83 Louisiana zones aren't included here: unlike the rest of this
+/// table, EPSG doesn't give Louisiana state plane a code in the expected
+/// 321xx sequence, and we don't have a verified code for it, so those
+/// grids fall back to `proj4_from_parameters` instead of risking a wrong
+/// EPSG code
+const KNOWN_EPSG: &[(&str, u32)] = &[
+    ("TX-27C", 32039), // Texas Central, NAD27
+    ("TX-83C", 32139), // Texas Central, NAD83
+    ("TX-27N", 32037), // Texas North, NAD27
+    ("TX-83N", 32137), // Texas North, NAD83
+    ("TX-27S", 32041), // Texas South, NAD27
+    ("TX-83S", 32141), // Texas South, NAD83
+    ("OK-27N", 32024), // Oklahoma North, NAD27
+    ("OK-83N", 32124), // Oklahoma North, NAD83
+    ("OK-27S", 32025), // Oklahoma South, NAD27
+    ("OK-83S", 32125), // Oklahoma South, NAD83
+    ("LA-27N", 26781), // Louisiana North, NAD27
+    ("LA-27S", 26782), // Louisiana South, NAD27
+];
+
+fn datum_to_proj(datum: &str) -> &str {
+    match datum {
+        "NAD27" => "NAD27",
+        "NAD83" => "NAD83",
+        "WGS84" => "WGS84",
+        _ => "WGS84",
+    }
+}
+
+fn units_to_proj(units: petra_grid::UnitOfMeasure) -> &'static str {
+    match units {
+        petra_grid::UnitOfMeasure::Feet => "us-ft",
+        petra_grid::UnitOfMeasure::Meters => "m",
+    }
+}
+
+/// the `projection` name prefixes we know to be transverse Mercator
+/// (rather than Lambert Conformal Conic); everything else falls back to
+/// LCC below, since that's what the TX/OK/LA state-plane zones this
+/// table targets actually are
+const TMERC_PREFIXES: &[&str] = &["UTM"];
+
+fn is_transverse_mercator(projection: &str) -> bool {
+    TMERC_PREFIXES.iter().any(|prefix| projection.starts_with(prefix))
+}
+
+/// build a PROJ string for a Lambert Conformal Conic / transverse
+/// Mercator projection from Petra's `cm` (central meridian), `rlat`
+/// (reference latitude), `datum`, and `xyunits`
+///
+/// we pick the projection family from `grid.projection`'s name (UTM
+/// zones are transverse Mercator; everything else we default to LCC,
+/// since that's what the state-plane zones in `KNOWN_EPSG` actually are)
+/// rather than decoding `projection_code`, which we haven't figured out
+/// yet; Petra's metadata doesn't give us a second standard parallel, so
+/// the LCC case is a tangent-cone approximation with `lat_1 = lat_2 =
+/// lat_0 = rlat`
+fn proj4_from_parameters(grid: &petra_grid::Grid) -> String {
+    if is_transverse_mercator(&grid.projection) {
+        format!(
+            "+proj=tmerc +lat_0={} +lon_0={} +datum={} +units={} +no_defs",
+            grid.rlat,
+            grid.cm,
+            datum_to_proj(&grid.datum),
+            units_to_proj(grid.xyunits),
+        )
+    } else {
+        format!(
+            "+proj=lcc +lat_1={} +lat_2={} +lat_0={} +lon_0={} +datum={} +units={} +no_defs",
+            grid.rlat,
+            grid.rlat,
+            grid.rlat,
+            grid.cm,
+            datum_to_proj(&grid.datum),
+            units_to_proj(grid.xyunits),
+        )
+    }
+}
+
+/// resolve a CRS for `grid` from its `projection`/`datum`/`cm`/`rlat`
+/// fields: an EPSG code (as a Python `int`) when `projection` matches a
+/// known entry, or a PROJ string (as a Python `str`) built from the
+/// numeric parameters otherwise
+pub fn crs(grid: &petra_grid::Grid) -> PyObject {
+    Python::with_gil(|py| {
+        for (name, epsg) in KNOWN_EPSG {
+            if *name == grid.projection {
+                return epsg.into_py(py);
+            }
+        }
+
+        proj4_from_parameters(grid).into_py(py)
+    })
+}
@@ -0,0 +1,136 @@
+use std::io::Write as _;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::filey::Filey;
+use crate::to_pyerr;
+
+/// the on-disk formats we know how to produce
+///
+/// `Native` is Petra's own GRD binary layout (a round-trip of whatever
+/// `read_grid` accepted); the rest are neutral formats meant for other
+/// tools to consume
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Petra's native GRD binary layout
+    Native,
+
+    /// OpenDX ASCII (`.dx`); rectangular grids only, for now
+    OpenDx,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Native => "grd",
+            Format::OpenDx => "dx",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "native" | "grd" | "grid" => Some(Format::Native),
+            "opendx" | "dx" => Some(Format::OpenDx),
+            _ => None,
+        }
+    }
+}
+
+/// write `grid` to `name_or_file_like`, inferring the format from the
+/// destination's extension unless `format` is given explicitly
+///
+/// when writing to a path (rather than a file-like object), the correct
+/// extension for the resolved format is appended if it isn't already
+/// present, so `grid.to_file("out", format="opendx")` and
+/// `grid.to_file("out.dx")` both produce `out.dx`; a path with no
+/// recognized extension and no `format` is an error
+pub fn write_grid(
+    grid: &petra_grid::Grid,
+    name_or_file_like: PyObject,
+    format: Option<&str>,
+) -> PyResult<()> {
+    let (path, fmt) = Python::with_gil(|py| -> PyResult<(Option<String>, Format)> {
+        let path = name_or_file_like.extract::<String>(py).ok();
+
+        let fmt = match format {
+            Some(name) => Format::from_name(name).ok_or_else(|| {
+                PyValueError::new_err(format!("unrecognized format: {}", name))
+            })?,
+            None => {
+                let ext = path.as_deref().and_then(|p| p.rsplit('.').next());
+                ext.and_then(Format::from_name).ok_or_else(|| {
+                    PyValueError::new_err(
+                        "could not infer a format from the destination; pass format=...",
+                    )
+                })?
+            }
+        };
+
+        Ok((path, fmt))
+    })?;
+
+    let target = match path {
+        Some(p) => {
+            let want_ext = fmt.extension();
+            let corrected = if p.rsplit('.').next() == Some(want_ext) {
+                p
+            } else {
+                format!("{}.{}", p, want_ext)
+            };
+            Python::with_gil(|py| corrected.into_py(py))
+        },
+        None => name_or_file_like,
+    };
+
+    let mut f = Filey::create(target)?;
+
+    match fmt {
+        Format::Native => grid.write(&mut f).map_err(to_pyerr),
+        Format::OpenDx => write_opendx(grid, &mut f),
+    }
+}
+
+/// write a rectangular grid as OpenDX ASCII: a `gridpositions` object
+/// built from `xmin`/`ymin`/`xstep`/`ystep`, an implicit `gridconnections`
+/// object, and the data itself in row-major order
+fn write_opendx(grid: &petra_grid::Grid, f: &mut Filey) -> PyResult<()> {
+    let arr = match &grid.data {
+        petra_grid::GridData::Rectangular(arr) => arr,
+        petra_grid::GridData::Triangular(_) => {
+            return Err(PyValueError::new_err(
+                "OpenDX export is only supported for rectangular grids",
+            ));
+        },
+    };
+
+    let rows = grid.rows;
+    let columns = grid.columns;
+
+    writeln!(f, "object 1 class gridpositions counts {} {}", rows, columns)?;
+    writeln!(f, "origin {} {}", grid.xmin, grid.ymin)?;
+    writeln!(f, "delta {} {}", 0.0, grid.ystep)?;
+    writeln!(f, "delta {} {}", grid.xstep, 0.0)?;
+
+    writeln!(f, "object 2 class gridconnections counts {} {}", rows, columns)?;
+
+    writeln!(
+        f,
+        "object 3 class array type double rank 0 items {} data follows",
+        rows as u64 * columns as u64
+    )?;
+    for row in arr.rows() {
+        for v in row {
+            writeln!(f, "{}", v)?;
+        }
+    }
+
+    writeln!(f, "attribute \"dep\" string \"positions\"")?;
+    writeln!(f)?;
+    writeln!(f, "object \"{}\" class field", grid.name)?;
+    writeln!(f, "component \"positions\" value 1")?;
+    writeln!(f, "component \"connections\" value 2")?;
+    writeln!(f, "component \"data\" value 3")?;
+
+    Ok(())
+}
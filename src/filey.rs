@@ -13,6 +13,8 @@ pub enum Filey {
 }
 
 impl Filey {
+    /// open `name_or_file_like` for reading; a path string is opened
+    /// with `File::open`
     pub fn from(name_or_file_like: PyObject) -> io::Result<Self> {
         Python::with_gil(|py| {
             if let Ok(path) = name_or_file_like.extract::<&str>(py) {
@@ -22,6 +24,18 @@ impl Filey {
             }
         })
     }
+
+    /// open `name_or_file_like` for writing; a path string is created
+    /// (or truncated, if it already exists) with `File::create`
+    pub fn create(name_or_file_like: PyObject) -> io::Result<Self> {
+        Python::with_gil(|py| {
+            if let Ok(path) = name_or_file_like.extract::<&str>(py) {
+                Ok(Self::RustFile(File::create(path)?))
+            } else {
+                Ok(Self::PyFileLike(name_or_file_like))
+            }
+        })
+    }
 }
 
 impl Read for Filey {
@@ -56,3 +70,29 @@ impl Seek for Filey {
         }
     }
 }
+
+impl Write for Filey {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Filey::RustFile(f) => f.write(buf),
+            Filey::PyFileLike(o) => {
+                Python::with_gil(|py| -> io::Result<usize> {
+                    let bytes = pyo3::types::PyBytes::new(py, buf);
+                    Ok(o.call_method1(py, "write", (bytes,))?.extract(py)?)
+                })
+            },
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Filey::RustFile(f) => f.flush(),
+            Filey::PyFileLike(o) => {
+                Python::with_gil(|py| {
+                    o.call_method0(py, "flush")?;
+                    Ok(())
+                })
+            },
+        }
+    }
+}
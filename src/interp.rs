@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use ndarray::Array3;
+use numpy::{PyReadonlyArray1, ToPyArray};
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+/// sample `grid` at world coordinates `x`/`y`, which may each be a Python
+/// scalar (float) or a 1-D numpy array; returns the same shape back
+///
+/// rectangular grids are bilinearly interpolated from the four nodes
+/// surrounding each point; triangular grids are barycentrically
+/// interpolated from the containing triangle (found via a uniform bucket
+/// spatial index over triangle centroids, built once and reused across
+/// all of the query points)
+///
+/// points outside the grid, or whose surrounding nodes/triangle include
+/// a null (`null_value`) cell, sample to `NaN`
+pub fn sample(
+    grid: &petra_grid::Grid,
+    x: &PyAny,
+    y: &PyAny,
+    null_value: f64,
+) -> PyResult<PyObject> {
+    let py = x.py();
+
+    if let (Ok(xs), Ok(ys)) = (x.extract::<f64>(), y.extract::<f64>()) {
+        return Ok(sample_one(grid, xs, ys, null_value).into_py(py));
+    }
+
+    let xs: PyReadonlyArray1<f64> = x.extract()?;
+    let ys: PyReadonlyArray1<f64> = y.extract()?;
+    let xs = xs.as_array();
+    let ys = ys.as_array();
+    if xs.len() != ys.len() {
+        return Err(PyValueError::new_err("x and y must have the same length"));
+    }
+
+    let index = match &grid.data {
+        petra_grid::GridData::Triangular(arr) => Some(TriangleIndex::build(arr)),
+        petra_grid::GridData::Rectangular(_) => None,
+    };
+
+    let out: Vec<f64> = xs.iter().zip(ys.iter())
+        .map(|(&xi, &yi)| sample_with_index(grid, &index, xi, yi, null_value))
+        .collect();
+
+    Ok(out.to_pyarray(py).into())
+}
+
+fn sample_one(grid: &petra_grid::Grid, x: f64, y: f64, null_value: f64) -> f64 {
+    let index = match &grid.data {
+        petra_grid::GridData::Triangular(arr) => Some(TriangleIndex::build(arr)),
+        petra_grid::GridData::Rectangular(_) => None,
+    };
+    sample_with_index(grid, &index, x, y, null_value)
+}
+
+fn sample_with_index(
+    grid: &petra_grid::Grid,
+    index: &Option<TriangleIndex>,
+    x: f64,
+    y: f64,
+    null_value: f64,
+) -> f64 {
+    match (&grid.data, index) {
+        (petra_grid::GridData::Rectangular(arr), _) =>
+            sample_rectangular(grid, arr, x, y, null_value),
+        (petra_grid::GridData::Triangular(arr), Some(idx)) =>
+            idx.sample(arr, x, y, null_value),
+        (petra_grid::GridData::Triangular(_), None) => unreachable!(),
+    }
+}
+
+fn sample_rectangular(
+    grid: &petra_grid::Grid,
+    arr: &ndarray::Array2<f64>,
+    x: f64,
+    y: f64,
+    null_value: f64,
+) -> f64 {
+    let rows = grid.rows as i64;
+    let columns = grid.columns as i64;
+    if rows < 2 || columns < 2 {
+        return f64::NAN;
+    }
+
+    let fx = (x - grid.xmin) / grid.xstep;
+    let fy = (y - grid.ymin) / grid.ystep;
+    if !fx.is_finite() || !fy.is_finite()
+        || fx < 0.0 || fy < 0.0
+        || fx > (columns - 1) as f64 || fy > (rows - 1) as f64
+    {
+        return f64::NAN;
+    }
+
+    let mut j = fx.floor() as i64;
+    let mut tx = fx - j as f64;
+    if j >= columns - 1 {
+        j = columns - 2;
+        tx = 1.0;
+    }
+
+    let mut i = fy.floor() as i64;
+    let mut ty = fy - i as f64;
+    if i >= rows - 1 {
+        i = rows - 2;
+        ty = 1.0;
+    }
+
+    if i < 0 || j < 0 {
+        return f64::NAN;
+    }
+
+    let (i, j) = (i as usize, j as usize);
+    let z00 = arr[[i, j]];
+    let z01 = arr[[i, j + 1]];
+    let z10 = arr[[i + 1, j]];
+    let z11 = arr[[i + 1, j + 1]];
+
+    if z00 == null_value || z01 == null_value || z10 == null_value || z11 == null_value {
+        return f64::NAN;
+    }
+
+    let z0 = z00 * (1.0 - tx) + z01 * tx;
+    let z1 = z10 * (1.0 - tx) + z11 * tx;
+    z0 * (1.0 - ty) + z1 * ty
+}
+
+/// a uniform bucket spatial index over triangle centroids, used to limit
+/// the point-in-triangle search to a handful of nearby candidates
+struct TriangleIndex {
+    xmin: f64,
+    ymin: f64,
+    cell_size: f64,
+    buckets: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl TriangleIndex {
+    fn build(arr: &Array3<f64>) -> Self {
+        let n = arr.shape()[0];
+
+        let mut xmin = f64::INFINITY;
+        let mut xmax = f64::NEG_INFINITY;
+        let mut ymin = f64::INFINITY;
+        let mut ymax = f64::NEG_INFINITY;
+        let mut centroids = Vec::with_capacity(n);
+
+        for t in 0..n {
+            let cx = (arr[[t, 0, 0]] + arr[[t, 1, 0]] + arr[[t, 2, 0]]) / 3.0;
+            let cy = (arr[[t, 0, 1]] + arr[[t, 1, 1]] + arr[[t, 2, 1]]) / 3.0;
+            xmin = xmin.min(cx);
+            xmax = xmax.max(cx);
+            ymin = ymin.min(cy);
+            ymax = ymax.max(cy);
+            centroids.push((cx, cy));
+        }
+
+        let span = (xmax - xmin).max(ymax - ymin).max(1.0);
+        let cell_size = span / (n as f64).max(1.0).sqrt().max(1.0);
+        let cell_size = if cell_size > 0.0 { cell_size } else { 1.0 };
+
+        let mut buckets: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (t, (cx, cy)) in centroids.into_iter().enumerate() {
+            let key = (
+                ((cx - xmin) / cell_size).floor() as i64,
+                ((cy - ymin) / cell_size).floor() as i64,
+            );
+            buckets.entry(key).or_default().push(t);
+        }
+
+        TriangleIndex { xmin, ymin, cell_size, buckets }
+    }
+
+    fn sample(&self, arr: &Array3<f64>, x: f64, y: f64, null_value: f64) -> f64 {
+        let cj = ((x - self.xmin) / self.cell_size).floor() as i64;
+        let ci = ((y - self.ymin) / self.cell_size).floor() as i64;
+
+        for radius in 0i64..8 {
+            for di in -radius..=radius {
+                for dj in -radius..=radius {
+                    // only the newly-added ring at this radius
+                    if radius > 0 && di.abs() != radius && dj.abs() != radius {
+                        continue;
+                    }
+                    if let Some(candidates) = self.buckets.get(&(cj + dj, ci + di)) {
+                        for &t in candidates {
+                            let x0 = arr[[t, 0, 0]];
+                            let y0 = arr[[t, 0, 1]];
+                            let z0 = arr[[t, 0, 2]];
+                            let x1 = arr[[t, 1, 0]];
+                            let y1 = arr[[t, 1, 1]];
+                            let z1 = arr[[t, 1, 2]];
+                            let x2 = arr[[t, 2, 0]];
+                            let y2 = arr[[t, 2, 1]];
+                            let z2 = arr[[t, 2, 2]];
+
+                            if let Some((l0, l1, l2)) =
+                                barycentric(x, y, x0, y0, x1, y1, x2, y2)
+                            {
+                                if z0 == null_value || z1 == null_value || z2 == null_value {
+                                    return f64::NAN;
+                                }
+                                return l0 * z0 + l1 * z1 + l2 * z2;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        f64::NAN
+    }
+}
+
+/// barycentric weights of `(px, py)` with respect to triangle
+/// `(x0,y0), (x1,y1), (x2,y2)`; `None` if the point lies outside the
+/// triangle (within a small tolerance) or the triangle is degenerate
+#[allow(clippy::too_many_arguments)]
+fn barycentric(
+    px: f64, py: f64,
+    x0: f64, y0: f64,
+    x1: f64, y1: f64,
+    x2: f64, y2: f64,
+) -> Option<(f64, f64, f64)> {
+    const EPS: f64 = 1e-9;
+
+    let denom = (y1 - y2) * (x0 - x2) + (x2 - x1) * (y0 - y2);
+    if denom.abs() < 1e-12 {
+        return None;
+    }
+
+    let l0 = ((y1 - y2) * (px - x2) + (x2 - x1) * (py - y2)) / denom;
+    let l1 = ((y2 - y0) * (px - x2) + (x0 - x2) * (py - y2)) / denom;
+    let l2 = 1.0 - l0 - l1;
+
+    if (-EPS..=1.0 + EPS).contains(&l0)
+        && (-EPS..=1.0 + EPS).contains(&l1)
+        && (-EPS..=1.0 + EPS).contains(&l2)
+    {
+        Some((l0.clamp(0.0, 1.0), l1.clamp(0.0, 1.0), l2.clamp(0.0, 1.0)))
+    } else {
+        None
+    }
+}
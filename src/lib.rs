@@ -30,6 +30,13 @@ use numpy::ToPyArray;
 mod filey;
 use filey::Filey;
 
+mod export;
+mod xr;
+mod crs;
+mod interp;
+mod triangulate;
+mod subset;
+
 /// units of measure for a given dimension
 ///
 /// the only known values are:
@@ -287,6 +294,113 @@ impl Grid {
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
+
+    /// write this grid to a file path or file-like object
+    ///
+    /// the format is inferred from the destination's extension unless
+    /// `format` is given explicitly (one of `"native"`/`"grd"` for Petra's
+    /// own binary layout, or `"opendx"`/`"dx"` for OpenDX ASCII); when
+    /// writing to a path, the correct extension for the resolved format
+    /// is appended if it isn't already present
+    ///
+    /// OpenDX export is currently only supported for rectangular grids
+    #[pyo3(signature = (name_or_file_like, format=None))]
+    fn to_file(&self, name_or_file_like: PyObject, format: Option<&str>) -> PyResult<()> {
+        export::write_grid(&self.0, name_or_file_like, format)
+    }
+
+    /// the `x`/`y` coordinate vectors for a rectangular grid, as a pair of
+    /// 1-D numpy arrays, computed from `xmin`/`xstep`/`columns` and
+    /// `ymin`/`ystep`/`rows`
+    #[getter]
+    fn get_coords(&self) -> PyResult<PyObject> {
+        xr::coords(&self.0)
+    }
+
+    /// wrap this grid's data as an `xarray.DataArray` with real `x`/`y`
+    /// coordinates and `xyunits`/`zunits`/`projection`/`datum` attributes
+    ///
+    /// Petra stamps "no data" cells with a sentinel value rather than
+    /// omitting them; cells equal to `null_value` are masked to `NaN` so
+    /// they don't leak into plots or statistics
+    ///
+    /// only supported for rectangular grids
+    #[pyo3(signature = (null_value=xr::DEFAULT_NULL_VALUE))]
+    fn to_xarray(&self, null_value: f64) -> PyResult<PyObject> {
+        xr::to_xarray(&self.0, null_value)
+    }
+
+    /// resolve a coordinate reference system from `projection`/`datum`/
+    /// `cm`/`rlat`: an `int` EPSG code when `projection` matches a known
+    /// Petra projection name (e.g. `"TX-27C"` -> `32039`), or else a PROJ
+    /// string built from the numeric parameters
+    fn crs(&self) -> PyObject {
+        crs::crs(&self.0)
+    }
+
+    /// interpolate `z` values at arbitrary world coordinates `x`/`y`,
+    /// each of which may be a scalar or a 1-D numpy array
+    ///
+    /// rectangular grids are bilinearly interpolated from the four
+    /// surrounding nodes; triangular grids are barycentrically
+    /// interpolated from the containing triangle (located via a spatial
+    /// index built once and reused across all of the query points)
+    ///
+    /// points outside the grid, or whose surrounding nodes/triangle
+    /// include a null (`null_value`) cell, sample to `NaN`
+    #[pyo3(signature = (x, y, null_value=xr::DEFAULT_NULL_VALUE))]
+    fn sample(&self, x: &PyAny, y: &PyAny, null_value: f64) -> PyResult<PyObject> {
+        interp::sample(&self.0, x, y, null_value)
+    }
+
+    /// turn this grid into the `(n_triangles, 3, 3)` vertex layout already
+    /// used for triangular grids; a no-op if it already is one
+    ///
+    /// rectangular grids are split two-triangles-per-cell by default; pass
+    /// `delaunay=True` for a true Delaunay triangulation over the non-null
+    /// nodes instead, which is the better choice once the grid has
+    /// masked-out cells
+    #[pyo3(signature = (null_value=xr::DEFAULT_NULL_VALUE, delaunay=false))]
+    fn triangulate(&self, null_value: f64, delaunay: bool) -> PyResult<PyObject> {
+        triangulate::triangulate(&self.0, null_value, delaunay)
+    }
+
+    /// a lighter-weight `triangulate`: a `(node_x, node_y, node_z,
+    /// triples)` tuple, where `triples` is an `(n_triangles, 3)` integer
+    /// array of indices into the flattened node arrays
+    ///
+    /// see `triangulate` for the meaning of `null_value` and `delaunay`
+    #[pyo3(signature = (null_value=xr::DEFAULT_NULL_VALUE, delaunay=false))]
+    fn delaunay_triples(&self, null_value: f64, delaunay: bool) -> PyResult<PyObject> {
+        triangulate::delaunay_triples(&self.0, null_value, delaunay)
+    }
+
+    /// clip this grid to the rectangular window `[xmin, xmax] x [ymin,
+    /// ymax]`, returning a new `Grid`
+    ///
+    /// for rectangular grids, the window is snapped to cell boundaries
+    /// (via `xstep`/`ystep`) and the underlying array is sliced, with
+    /// `null_value` excluded when recomputing `zmin`/`zmax`; for
+    /// triangular grids, triangles whose centroid falls outside the
+    /// window are dropped
+    ///
+    /// a window that doesn't overlap the grid at all yields an empty
+    /// grid rather than an error
+    #[pyo3(signature = (xmin, xmax, ymin, ymax, null_value=xr::DEFAULT_NULL_VALUE))]
+    fn subset(&self, xmin: f64, xmax: f64, ymin: f64, ymax: f64, null_value: f64) -> PyResult<Grid> {
+        subset::subset(&self.0, xmin, xmax, ymin, ymax, null_value)
+    }
+
+    /// mask cells outside an arbitrary polygon (a list of `(x, y)`
+    /// vertices) to `null_value`, returning a new `Grid`
+    ///
+    /// for rectangular grids this nulls out the masked cells in place;
+    /// for triangular grids, entire triangles whose centroid falls
+    /// outside the polygon are dropped
+    #[pyo3(signature = (vertices, null_value=xr::DEFAULT_NULL_VALUE))]
+    fn clip_to_polygon(&self, vertices: Vec<(f64, f64)>, null_value: f64) -> PyResult<Grid> {
+        subset::clip_to_polygon(&self.0, vertices, null_value)
+    }
 }
 
 /// read a Petra grid from a file path or file-like object
@@ -296,6 +410,14 @@ fn read_grid(name_or_file_like: PyObject) -> PyResult<Grid> {
     Ok(Grid(petra_grid::Grid::read(&mut f).map_err(to_pyerr)?))
 }
 
+/// write a Petra grid to a file path or file-like object; see
+/// `Grid.to_file` for details on format inference and extension handling
+#[pyfunction]
+#[pyo3(signature = (grid, name_or_file_like, format=None))]
+fn write_grid(grid: &Grid, name_or_file_like: PyObject, format: Option<&str>) -> PyResult<()> {
+    export::write_grid(&grid.0, name_or_file_like, format)
+}
+
 /// types and functions for retrieving (partial) grid data from Petra GRD files
 ///
 /// this library is based on a lot of time spent in a hex editor examining some
@@ -316,10 +438,11 @@ fn petra_grid_py(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<UnitOfMeasure>()?;
     m.add_class::<Grid>()?;
     m.add_function(wrap_pyfunction!(read_grid, m)?)?;
+    m.add_function(wrap_pyfunction!(write_grid, m)?)?;
     Ok(())
 }
 
-fn to_pyerr(err: petra_grid::Error) -> PyErr {
+pub(crate) fn to_pyerr(err: petra_grid::Error) -> PyErr {
     match err {
         petra_grid::Error::IOError(e) => e.into(),
         _ => PyValueError::new_err(format!("{}", err)),
@@ -0,0 +1,240 @@
+use ndarray::{Array2, Array3};
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+
+use crate::Grid;
+
+/// clip `grid` to the rectangular window `[xmin, xmax] x [ymin, ymax]`,
+/// snapping the window to cell boundaries (via `xstep`/`ystep`) for
+/// rectangular grids, and filtering by triangle centroid for triangular
+/// ones; a window that doesn't overlap the grid at all yields an empty
+/// grid (zero rows/columns or zero triangles) rather than an error
+///
+/// `null_value` is excluded when recomputing `zmin`/`zmax` for the
+/// subsetted rectangular grid, so a window that happens to include
+/// no-data cells doesn't drag the new bounds down to the sentinel
+pub fn subset(
+    grid: &petra_grid::Grid,
+    xmin: f64,
+    xmax: f64,
+    ymin: f64,
+    ymax: f64,
+    null_value: f64,
+) -> PyResult<Grid> {
+    match &grid.data {
+        petra_grid::GridData::Rectangular(arr) => {
+            let columns = grid.columns as i64;
+            let rows = grid.rows as i64;
+
+            let col_of = |x: f64| ((x - grid.xmin) / grid.xstep).round() as i64;
+            let row_of = |y: f64| ((y - grid.ymin) / grid.ystep).round() as i64;
+
+            let raw_j0 = col_of(xmin);
+            let raw_j1 = col_of(xmax);
+            let raw_i0 = row_of(ymin);
+            let raw_i1 = row_of(ymax);
+
+            let overlaps = raw_j0 <= raw_j1 && raw_i0 <= raw_i1
+                && raw_j1 >= 0 && raw_j0 < columns
+                && raw_i1 >= 0 && raw_i0 < rows;
+
+            if !overlaps {
+                return Ok(Grid(petra_grid::Grid {
+                    rows: 0,
+                    columns: 0,
+                    size: 0,
+                    xmin, xmax, ymin, ymax,
+                    zmin: f64::INFINITY,
+                    zmax: f64::NEG_INFINITY,
+                    data: petra_grid::GridData::Rectangular(Array2::zeros((0, 0))),
+                    ..clone_metadata(grid)
+                }));
+            }
+
+            let j0 = raw_j0.max(0) as usize;
+            let j1 = raw_j1.min(columns - 1) as usize;
+            let i0 = raw_i0.max(0) as usize;
+            let i1 = raw_i1.min(rows - 1) as usize;
+
+            let sliced = arr.slice(ndarray::s![i0..=i1, j0..=j1]).to_owned();
+            let new_rows = (i1 - i0 + 1) as u32;
+            let new_columns = (j1 - j0 + 1) as u32;
+            let new_xmin = grid.xmin + j0 as f64 * grid.xstep;
+            let new_xmax = grid.xmin + j1 as f64 * grid.xstep;
+            let new_ymin = grid.ymin + i0 as f64 * grid.ystep;
+            let new_ymax = grid.ymin + i1 as f64 * grid.ystep;
+
+            let zmin = sliced.iter().cloned().filter(|&v| v != null_value)
+                .fold(f64::INFINITY, f64::min);
+            let zmax = sliced.iter().cloned().filter(|&v| v != null_value)
+                .fold(f64::NEG_INFINITY, f64::max);
+
+            Ok(Grid(petra_grid::Grid {
+                rows: new_rows,
+                columns: new_columns,
+                size: new_rows * new_columns,
+                xmin: new_xmin,
+                xmax: new_xmax,
+                ymin: new_ymin,
+                ymax: new_ymax,
+                zmin,
+                zmax,
+                data: petra_grid::GridData::Rectangular(sliced),
+                ..clone_metadata(grid)
+            }))
+        },
+        petra_grid::GridData::Triangular(arr) => {
+            let kept = filter_triangles(arr, |cx, cy| {
+                cx >= xmin && cx <= xmax && cy >= ymin && cy <= ymax
+            });
+            Ok(Grid(rebuild_triangular(grid, kept)))
+        },
+    }
+}
+
+/// mask cells outside an arbitrary polygon (a list of `(x, y)` vertices)
+/// to `null_value`, for rectangular grids; for triangular grids, entire
+/// triangles whose centroid falls outside the polygon are dropped
+pub fn clip_to_polygon(
+    grid: &petra_grid::Grid,
+    vertices: Vec<(f64, f64)>,
+    null_value: f64,
+) -> PyResult<Grid> {
+    if vertices.len() < 3 {
+        return Err(PyValueError::new_err(
+            "clip_to_polygon needs at least 3 vertices",
+        ));
+    }
+
+    match &grid.data {
+        petra_grid::GridData::Rectangular(arr) => {
+            let mut masked = arr.clone();
+            for i in 0..grid.rows as usize {
+                let y = grid.ymin + i as f64 * grid.ystep;
+                for j in 0..grid.columns as usize {
+                    let x = grid.xmin + j as f64 * grid.xstep;
+                    if !point_in_polygon(x, y, &vertices) {
+                        masked[[i, j]] = null_value;
+                    }
+                }
+            }
+
+            Ok(Grid(petra_grid::Grid {
+                data: petra_grid::GridData::Rectangular(masked),
+                ..clone_metadata(grid)
+            }))
+        },
+        petra_grid::GridData::Triangular(arr) => {
+            let kept = filter_triangles(arr, |cx, cy| point_in_polygon(cx, cy, &vertices));
+            Ok(Grid(rebuild_triangular(grid, kept)))
+        },
+    }
+}
+
+/// copy every field of `grid` except `data`, which is left as an empty
+/// placeholder for the caller to overwrite; used to build a derived
+/// `Grid` without paying for a clone of the (potentially huge) original
+/// data array
+fn clone_metadata(grid: &petra_grid::Grid) -> petra_grid::Grid {
+    petra_grid::Grid {
+        version: grid.version,
+        name: grid.name.clone(),
+        size: grid.size,
+        rows: grid.rows,
+        columns: grid.columns,
+        n_triangles: grid.n_triangles,
+        xmin: grid.xmin,
+        xmax: grid.xmax,
+        ymin: grid.ymin,
+        ymax: grid.ymax,
+        xstep: grid.xstep,
+        ystep: grid.ystep,
+        zmin: grid.zmin,
+        zmax: grid.zmax,
+        xyunits: grid.xyunits,
+        zunits: grid.zunits,
+        created_date: grid.created_date,
+        source_data: grid.source_data.clone(),
+        unknown_metadata: grid.unknown_metadata.clone(),
+        projection: grid.projection.clone(),
+        datum: grid.datum.clone(),
+        grid_method: grid.grid_method,
+        projection_code: grid.projection_code,
+        cm: grid.cm,
+        rlat: grid.rlat,
+        data: petra_grid::GridData::Rectangular(Array2::zeros((0, 0))),
+    }
+}
+
+fn filter_triangles(arr: &Array3<f64>, keep: impl Fn(f64, f64) -> bool) -> Array3<f64> {
+    let n = arr.shape()[0];
+    let mut kept = Vec::new();
+    for t in 0..n {
+        let cx = (arr[[t, 0, 0]] + arr[[t, 1, 0]] + arr[[t, 2, 0]]) / 3.0;
+        let cy = (arr[[t, 0, 1]] + arr[[t, 1, 1]] + arr[[t, 2, 1]]) / 3.0;
+        if keep(cx, cy) {
+            kept.push(t);
+        }
+    }
+
+    let mut out = Array3::<f64>::zeros((kept.len(), 3, 3));
+    for (new_t, &old_t) in kept.iter().enumerate() {
+        for v in 0..3 {
+            for d in 0..3 {
+                out[[new_t, v, d]] = arr[[old_t, v, d]];
+            }
+        }
+    }
+    out
+}
+
+fn rebuild_triangular(grid: &petra_grid::Grid, kept: Array3<f64>) -> petra_grid::Grid {
+    let n_triangles = kept.shape()[0] as u32;
+
+    let mut xmin = f64::INFINITY;
+    let mut xmax = f64::NEG_INFINITY;
+    let mut ymin = f64::INFINITY;
+    let mut ymax = f64::NEG_INFINITY;
+    let mut zmin = f64::INFINITY;
+    let mut zmax = f64::NEG_INFINITY;
+    for t in 0..kept.shape()[0] {
+        for v in 0..3 {
+            xmin = xmin.min(kept[[t, v, 0]]);
+            xmax = xmax.max(kept[[t, v, 0]]);
+            ymin = ymin.min(kept[[t, v, 1]]);
+            ymax = ymax.max(kept[[t, v, 1]]);
+            zmin = zmin.min(kept[[t, v, 2]]);
+            zmax = zmax.max(kept[[t, v, 2]]);
+        }
+    }
+
+    petra_grid::Grid {
+        n_triangles,
+        xmin,
+        xmax,
+        ymin,
+        ymax,
+        zmin,
+        zmax,
+        data: petra_grid::GridData::Triangular(kept),
+        ..clone_metadata(grid)
+    }
+}
+
+/// standard even-odd ray-casting point-in-polygon test
+fn point_in_polygon(x: f64, y: f64, vertices: &[(f64, f64)]) -> bool {
+    let n = vertices.len();
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let (xi, yi) = vertices[i];
+        let (xj, yj) = vertices[j];
+        if (yi > y) != (yj > y)
+            && x < (xj - xi) * (y - yi) / (yj - yi) + xi
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
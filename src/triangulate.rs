@@ -0,0 +1,268 @@
+use ndarray::Array3;
+use numpy::ToPyArray;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+/// flattened `(x, y, z)` node coordinates, alongside the index triples
+/// into them
+type NodesAndTriples = (Vec<(f64, f64, f64)>, Vec<[usize; 3]>);
+
+/// turn `grid` into the same `(n_triangles, 3, 3)` vertex/dimension
+/// layout already used for `GridData::Triangular` grids
+///
+/// triangular grids are returned as-is; rectangular grids are
+/// triangulated as described on `delaunay_triples`
+pub fn triangulate(grid: &petra_grid::Grid, null_value: f64, delaunay: bool) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        match &grid.data {
+            petra_grid::GridData::Triangular(arr) => Ok(arr.to_pyarray(py).into()),
+            petra_grid::GridData::Rectangular(arr) => {
+                let (nodes, triples) = if delaunay {
+                    delaunay_nodes_and_triples(grid, arr, null_value)
+                } else {
+                    grid_nodes_and_triples(grid, arr, null_value)
+                };
+
+                let mut verts = Array3::<f64>::zeros((triples.len(), 3, 3));
+                for (t, tri) in triples.iter().enumerate() {
+                    for (v, &n) in tri.iter().enumerate() {
+                        let (x, y, z) = nodes[n];
+                        verts[[t, v, 0]] = x;
+                        verts[[t, v, 1]] = y;
+                        verts[[t, v, 2]] = z;
+                    }
+                }
+
+                Ok(verts.to_pyarray(py).into())
+            },
+        }
+    })
+}
+
+/// a lighter-weight version of `triangulate`: flattened `(x, y, z)` node
+/// arrays, plus an `(n_triangles, 3)` integer array of node indices
+///
+/// for a rectangular grid, each cell is split into two counterclockwise
+/// triangles by default (trivial, since the grid is already regular);
+/// pass `delaunay=True` to instead run a true Delaunay triangulation
+/// over the non-null nodes, which is more appropriate once the grid has
+/// masked-out cells and the naive per-cell split would leave holes with
+/// jagged boundaries
+///
+/// for a triangular grid, the node arrays are simply the (unshared,
+/// un-deduplicated) triangle vertices already present in `data`
+pub fn delaunay_triples(
+    grid: &petra_grid::Grid,
+    null_value: f64,
+    delaunay: bool,
+) -> PyResult<PyObject> {
+    let (nodes, triples) = match &grid.data {
+        petra_grid::GridData::Rectangular(arr) => {
+            if delaunay {
+                delaunay_nodes_and_triples(grid, arr, null_value)
+            } else {
+                grid_nodes_and_triples(grid, arr, null_value)
+            }
+        },
+        petra_grid::GridData::Triangular(arr) => triangular_nodes_and_triples(arr),
+    };
+
+    Python::with_gil(|py| {
+        let xs: Vec<f64> = nodes.iter().map(|n| n.0).collect();
+        let ys: Vec<f64> = nodes.iter().map(|n| n.1).collect();
+        let zs: Vec<f64> = nodes.iter().map(|n| n.2).collect();
+
+        let mut idx = ndarray::Array2::<i64>::zeros((triples.len(), 3));
+        for (t, tri) in triples.iter().enumerate() {
+            for (v, &n) in tri.iter().enumerate() {
+                idx[[t, v]] = n as i64;
+            }
+        }
+
+        Ok(PyTuple::new(py, [
+            xs.to_pyarray(py).to_object(py),
+            ys.to_pyarray(py).to_object(py),
+            zs.to_pyarray(py).to_object(py),
+            idx.to_pyarray(py).to_object(py),
+        ]).into())
+    })
+}
+
+fn triangular_nodes_and_triples(arr: &Array3<f64>) -> NodesAndTriples {
+    let n = arr.shape()[0];
+    let mut nodes = Vec::with_capacity(n * 3);
+    let mut triples = Vec::with_capacity(n);
+    for t in 0..n {
+        let base = nodes.len();
+        for v in 0..3 {
+            nodes.push((arr[[t, v, 0]], arr[[t, v, 1]], arr[[t, v, 2]]));
+        }
+        triples.push([base, base + 1, base + 2]);
+    }
+    (nodes, triples)
+}
+
+/// split each cell of a rectangular grid into two counterclockwise
+/// triangles, skipping any triangle that touches a null-sentinel node
+fn grid_nodes_and_triples(
+    grid: &petra_grid::Grid,
+    arr: &ndarray::Array2<f64>,
+    null_value: f64,
+) -> NodesAndTriples {
+    let rows = grid.rows as usize;
+    let columns = grid.columns as usize;
+
+    let mut nodes = Vec::with_capacity(rows * columns);
+    for i in 0..rows {
+        let y = grid.ymin + i as f64 * grid.ystep;
+        for j in 0..columns {
+            let x = grid.xmin + j as f64 * grid.xstep;
+            nodes.push((x, y, arr[[i, j]]));
+        }
+    }
+
+    let idx = |i: usize, j: usize| i * columns + j;
+    let mut triples = Vec::new();
+
+    if rows >= 2 && columns >= 2 {
+        for i in 0..rows - 1 {
+            for j in 0..columns - 1 {
+                let n00 = idx(i, j);
+                let n01 = idx(i, j + 1);
+                let n10 = idx(i + 1, j);
+                let n11 = idx(i + 1, j + 1);
+
+                let not_null = |n: usize| nodes[n].2 != null_value;
+
+                if not_null(n00) && not_null(n01) && not_null(n11) {
+                    triples.push([n00, n01, n11]);
+                }
+                if not_null(n00) && not_null(n11) && not_null(n10) {
+                    triples.push([n00, n11, n10]);
+                }
+            }
+        }
+    }
+
+    (nodes, triples)
+}
+
+/// a true Delaunay triangulation (Bowyer-Watson) over the non-null nodes
+/// of a rectangular grid
+fn delaunay_nodes_and_triples(
+    grid: &petra_grid::Grid,
+    arr: &ndarray::Array2<f64>,
+    null_value: f64,
+) -> NodesAndTriples {
+    let rows = grid.rows as usize;
+    let columns = grid.columns as usize;
+
+    let mut nodes = Vec::new();
+    for i in 0..rows {
+        let y = grid.ymin + i as f64 * grid.ystep;
+        for j in 0..columns {
+            let x = grid.xmin + j as f64 * grid.xstep;
+            let z = arr[[i, j]];
+            if z != null_value {
+                nodes.push((x, y, z));
+            }
+        }
+    }
+
+    let points: Vec<(f64, f64)> = nodes.iter().map(|&(x, y, _)| (x, y)).collect();
+    let triples = bowyer_watson(&points);
+    (nodes, triples)
+}
+
+/// incremental Bowyer-Watson Delaunay triangulation of a point set,
+/// returned as counterclockwise-wound index triples
+fn bowyer_watson(points: &[(f64, f64)]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    let (mut minx, mut maxx) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut miny, mut maxy) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &(x, y) in points {
+        minx = minx.min(x);
+        maxx = maxx.max(x);
+        miny = miny.min(y);
+        maxy = maxy.max(y);
+    }
+    let dmax = (maxx - minx).max(maxy - miny).max(1.0);
+    let (midx, midy) = ((minx + maxx) / 2.0, (miny + maxy) / 2.0);
+
+    // a "super triangle" that strictly contains every input point
+    let mut pts = points.to_vec();
+    let super_base = pts.len();
+    pts.push((midx - 20.0 * dmax, midy - dmax));
+    pts.push((midx, midy + 20.0 * dmax));
+    pts.push((midx + 20.0 * dmax, midy - dmax));
+
+    let mut triangles: Vec<[usize; 3]> =
+        vec![[super_base, super_base + 1, super_base + 2]];
+
+    for pi in 0..points.len() {
+        let p = pts[pi];
+
+        let bad: Vec<usize> = triangles.iter().enumerate()
+            .filter(|&(_, &tri)| in_circumcircle(p, pts[tri[0]], pts[tri[1]], pts[tri[2]]))
+            .map(|(ti, _)| ti)
+            .collect();
+
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for &ti in &bad {
+            let tri = triangles[ti];
+            edges.push((tri[0], tri[1]));
+            edges.push((tri[1], tri[2]));
+            edges.push((tri[2], tri[0]));
+        }
+
+        // keep only edges not shared by two bad triangles: the boundary
+        // of the polygonal hole left behind once they're removed
+        let boundary: Vec<(usize, usize)> = edges.iter().copied()
+            .filter(|&(a, b)| {
+                edges.iter().filter(|&&(c, d)| (c, d) == (a, b) || (c, d) == (b, a)).count() == 1
+            })
+            .collect();
+
+        let mut bad_sorted = bad;
+        bad_sorted.sort_unstable_by(|a, b| b.cmp(a));
+        for ti in bad_sorted {
+            triangles.remove(ti);
+        }
+
+        for (a, b) in boundary {
+            triangles.push([a, b, pi]);
+        }
+    }
+
+    triangles.retain(|t| t[0] < super_base && t[1] < super_base && t[2] < super_base);
+
+    for t in triangles.iter_mut() {
+        let (x0, y0) = pts[t[0]];
+        let (x1, y1) = pts[t[1]];
+        let (x2, y2) = pts[t[2]];
+        let cross = (x1 - x0) * (y2 - y0) - (y1 - y0) * (x2 - x0);
+        if cross < 0.0 {
+            t.swap(1, 2);
+        }
+    }
+
+    triangles
+}
+
+/// is `p` inside the circumcircle of `(a, b, c)`? either winding is
+/// accepted; the sign test is flipped for clockwise triangles
+fn in_circumcircle(p: (f64, f64), a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> bool {
+    let (ax, ay) = (a.0 - p.0, a.1 - p.1);
+    let (bx, by) = (b.0 - p.0, b.1 - p.1);
+    let (cx, cy) = (c.0 - p.0, c.1 - p.1);
+
+    let det = (ax * ax + ay * ay) * (bx * cy - cx * by)
+        - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay);
+
+    let orient = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+    if orient > 0.0 { det > 0.0 } else { det < 0.0 }
+}
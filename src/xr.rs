@@ -0,0 +1,73 @@
+use ndarray::Array1;
+use numpy::ToPyArray;
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::PyDict;
+
+/// Petra seems to stamp "no data" cells with an enormous sentinel value
+/// rather than leaving them out of the grid entirely; we haven't found
+/// this documented anywhere, but `1e30` is the largest-magnitude value
+/// we've observed in the wild, so we use it as the default
+pub const DEFAULT_NULL_VALUE: f64 = 1e30;
+
+fn axis(origin: f64, step: f64, n: u32) -> Array1<f64> {
+    Array1::from_iter((0..n).map(|i| origin + i as f64 * step))
+}
+
+/// build an `xarray.DataArray` for a rectangular grid, masking cells
+/// equal to `null_value` to `NaN` and attaching unit/projection metadata
+pub fn to_xarray(grid: &petra_grid::Grid, null_value: f64) -> PyResult<PyObject> {
+    let arr = match &grid.data {
+        petra_grid::GridData::Rectangular(arr) => arr,
+        petra_grid::GridData::Triangular(_) => {
+            return Err(PyValueError::new_err(
+                "to_xarray is only supported for rectangular grids",
+            ));
+        },
+    };
+
+    let masked = arr.mapv(|v| if v == null_value { f64::NAN } else { v });
+    let xs = axis(grid.xmin, grid.xstep, grid.columns);
+    let ys = axis(grid.ymin, grid.ystep, grid.rows);
+
+    Python::with_gil(|py| {
+        let xarray = py.import("xarray")?;
+
+        let coords = PyDict::new(py);
+        coords.set_item("y", ys.to_pyarray(py))?;
+        coords.set_item("x", xs.to_pyarray(py))?;
+
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("dims", ("y", "x"))?;
+        kwargs.set_item("coords", coords)?;
+        kwargs.set_item("name", grid.name.as_str())?;
+
+        let data_array = xarray.getattr("DataArray")?.call(
+            (masked.to_pyarray(py),), Some(kwargs))?;
+
+        let attrs = data_array.getattr("attrs")?;
+        attrs.set_item("xyunits", format!("{:?}", grid.xyunits))?;
+        attrs.set_item("zunits", format!("{:?}", grid.zunits))?;
+        attrs.set_item("projection", grid.projection.as_str())?;
+        attrs.set_item("datum", grid.datum.as_str())?;
+
+        Ok(data_array.into())
+    })
+}
+
+/// the `x`/`y` coordinate vectors for a rectangular grid, as a pair of
+/// 1-D numpy arrays
+pub fn coords(grid: &petra_grid::Grid) -> PyResult<PyObject> {
+    if let petra_grid::GridData::Triangular(_) = &grid.data {
+        return Err(PyValueError::new_err(
+            "coords is only supported for rectangular grids",
+        ));
+    }
+
+    let xs = axis(grid.xmin, grid.xstep, grid.columns);
+    let ys = axis(grid.ymin, grid.ystep, grid.rows);
+
+    Python::with_gil(|py| {
+        Ok((xs.to_pyarray(py), ys.to_pyarray(py)).into_py(py))
+    })
+}